@@ -21,6 +21,7 @@ use crate::bindings;
 use crate::device::Device;
 use crate::error::{Error, Result, to_result, from_kernel_err_ptr};
 use crate::str::CStr;
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::ffi::c_void;
 use core::ptr;
@@ -28,6 +29,18 @@ use core::ptr;
 #[cfg(CONFIG_REGMAP_MMIO)]
 use crate::io_mem::IoMem;
 
+// `crate::spi::SpiDevice` and `crate::i2c::I2cClient` don't exist in this
+// tree yet, so `from_spi`/`from_i2c` below are additionally gated on
+// `any()` (always false) on top of their real Kconfig gates, and these
+// imports along with them, so neither can be selected for compilation
+// before those modules land. Drop the `any()` half of the gate together
+// with landing rust/kernel/spi.rs and rust/kernel/i2c.rs.
+#[cfg(all(CONFIG_REGMAP_SPI, any()))]
+use crate::spi::SpiDevice;
+
+#[cfg(all(CONFIG_REGMAP_I2C, any()))]
+use crate::i2c::I2cClient;
+
 #[derive(Copy, Clone)]
 pub enum RegmapEndian {
 	RegmapEndianDefault = 0,
@@ -75,6 +88,21 @@ type RegmapRange = bindings::regmap_range;
 //     range_max: u32
 // }
 
+/// One entry of a declarative, ordered register-write sequence, as used to
+/// express device init/patch sequences without hand-rolled sleeps between
+/// writes.
+///
+/// Safety: must have the same layout as `bindings::reg_sequence`.
+#[repr(C)]
+pub struct RegSequence {
+    /// Register address.
+    pub reg: u32,
+    /// Value to write.
+    pub val: u32,
+    /// Microseconds to sleep after this write, or 0 for none.
+    pub delay_us: u32,
+}
+
 pub struct RegmapAccessTable<'a> {
     yes_ranges: &'a[RegmapRange],
     no_ranges: &'a[RegmapRange]
@@ -463,18 +491,224 @@ impl<'a> RegmapConfig<'a> {
 }
 
 
+/// Register access policy callbacks, used in place of the range tables on
+/// [`RegmapConfig`] when whether a register is writeable/readable/volatile/
+/// precious cannot be expressed as a set of ranges.
+///
+/// Every method has a default: all registers are writeable and readable, and
+/// none is volatile, precious or no-increment writeable/readable. A driver
+/// only needs to override the ones it actually cares about.
+pub trait RegmapCallbacks {
+    /// Returns whether `reg` can be written.
+    fn writeable(&self, _dev: &Device, _reg: u32) -> bool {
+        true
+    }
+    /// Returns whether `reg` can be read.
+    fn readable(&self, _dev: &Device, _reg: u32) -> bool {
+        true
+    }
+    /// Returns whether `reg`'s value can't be cached.
+    fn volatile(&self, _dev: &Device, _reg: u32) -> bool {
+        false
+    }
+    /// Returns whether `reg` must not be read outside of an explicit driver
+    /// request (e.g. a clear-on-read interrupt status register).
+    fn precious(&self, _dev: &Device, _reg: u32) -> bool {
+        false
+    }
+    /// Returns whether `reg` supports multiple writes without incrementing
+    /// the register number.
+    fn writeable_noinc(&self, _dev: &Device, _reg: u32) -> bool {
+        false
+    }
+    /// Returns whether `reg` supports multiple reads without incrementing
+    /// the register number.
+    fn readable_noinc(&self, _dev: &Device, _reg: u32) -> bool {
+        false
+    }
+}
+
+/// Associates each device that has [`RegmapCallbacks`] installed with the
+/// boxed callbacks object for it.
+///
+/// `regmap`'s predicate callbacks only take `(dev, reg)` — there is no spare
+/// context pointer to stash the callbacks object in. `dev`'s driver data is
+/// exactly what a normal driver already uses for its own state, so stealing
+/// it would corrupt that state; this table is this module's own side
+/// channel and never touches `dev`'s driver data.
+crate::sync::global_lock! {
+    static REGMAP_CALLBACKS: SpinLock<Vec<(*mut bindings::device, *mut Box<dyn RegmapCallbacks>)>> = Vec::new();
+}
+
+/// Looks up the callbacks object installed for `dev`, if any.
+fn lookup_callbacks(dev: *mut bindings::device) -> Option<*mut Box<dyn RegmapCallbacks>> {
+    REGMAP_CALLBACKS.lock().iter().find(|(d, _)| *d == dev).map(|(_, ctx)| *ctx)
+}
+
+/// Safety: `ctx`, once returned by `lookup_callbacks`, stays valid until its
+/// owning `Regmap` removes it via `remove_callbacks` (which only happens
+/// after `regmap_exit` has returned or regmap init has failed, so it cannot
+/// race a trampoline call).
+unsafe extern "C" fn writeable_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return true };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.writeable(&dev, reg)
+}
+
+/// Safety: see [`writeable_reg_trampoline`].
+unsafe extern "C" fn readable_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return true };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.readable(&dev, reg)
+}
+
+/// Safety: see [`writeable_reg_trampoline`].
+unsafe extern "C" fn volatile_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return false };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.volatile(&dev, reg)
+}
+
+/// Safety: see [`writeable_reg_trampoline`].
+unsafe extern "C" fn precious_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return false };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.precious(&dev, reg)
+}
+
+/// Safety: see [`writeable_reg_trampoline`].
+unsafe extern "C" fn writeable_noinc_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return false };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.writeable_noinc(&dev, reg)
+}
+
+/// Safety: see [`writeable_reg_trampoline`].
+unsafe extern "C" fn readable_noinc_reg_trampoline(dev: *mut bindings::device, reg: u32) -> bool {
+    let Some(ctx) = lookup_callbacks(dev) else { return false };
+    // Safety: see function safety comment.
+    let callbacks = unsafe { &*ctx };
+    // Safety: dev is a valid, non-null struct device for the duration of the call.
+    let dev = unsafe { Device::from_raw(dev) };
+    callbacks.readable_noinc(&dev, reg)
+}
+
+/// `regmap` checks a predicate callback before falling back to its access
+/// table, so combining a [`RegmapCallbacks`] with the corresponding table
+/// would silently make the table dead code (e.g. every register becomes
+/// writeable despite a `wr_table`, since the default [`RegmapCallbacks::writeable`]
+/// is permissive). Refuse the combination outright rather than let it
+/// silently shadow the table.
+fn check_callbacks_compatible(config: &RegmapConfig<'_>) -> Result<()> {
+    if config.wr_table.is_some()
+        || config.rd_table.is_some()
+        || config.volatile_table.is_some()
+        || config.precious_table.is_some()
+        || config.wr_noinc_table.is_some()
+        || config.rd_noinc_table.is_some()
+    {
+        return Err(Error::from_kernel_errno(-(bindings::EINVAL as i32)));
+    }
+    Ok(())
+}
+
+/// Wires up all six predicate trampolines on `config`. Only ever called
+/// when callbacks are present; see `check_callbacks_compatible` for why the
+/// two are mutually exclusive with the corresponding access tables.
+///
+/// Safety: none; this only rewrites function-pointer fields of `config`.
+fn install_callback_trampolines(config: &mut bindings::regmap_config) {
+    config.writeable_reg = Some(writeable_reg_trampoline);
+    config.readable_reg = Some(readable_reg_trampoline);
+    config.volatile_reg = Some(volatile_reg_trampoline);
+    config.precious_reg = Some(precious_reg_trampoline);
+    config.writeable_noinc_reg = Some(writeable_noinc_reg_trampoline);
+    config.readable_noinc_reg = Some(readable_noinc_reg_trampoline);
+}
+
+/// Installs `callbacks` into the global callbacks table for `dev` and
+/// returns the raw pointer the trampolines will recover it through.
+///
+/// Safety: the returned pointer must be passed to `remove_callbacks` no
+/// earlier than the `regmap_exit` that stops the trampolines from firing.
+unsafe fn install_callbacks(
+    dev: *mut bindings::device,
+    callbacks: Box<dyn RegmapCallbacks>,
+) -> Result<*mut Box<dyn RegmapCallbacks>> {
+    let ctx = Box::into_raw(Box::try_new(callbacks)?);
+    REGMAP_CALLBACKS.lock().try_push((dev, ctx))?;
+    Ok(ctx)
+}
+
+/// Removes `entry`'s callbacks object from the global table and frees it,
+/// used both on the `from_*` constructors' error path (when regmap init
+/// itself fails) and on `Regmap::drop`.
+///
+/// Safety: `entry`'s pointer, if present, must not be read again after this
+/// call; in particular the trampolines must no longer be able to fire for
+/// it (i.e. `regmap_exit` must already have returned, or init must have
+/// failed before the trampolines were ever reachable).
+unsafe fn remove_callbacks(entry: Option<(*mut bindings::device, *mut Box<dyn RegmapCallbacks>)>) {
+    if let Some((dev, ctx)) = entry {
+        let mut table = REGMAP_CALLBACKS.lock();
+        if let Some(pos) = table.iter().position(|(d, c)| *d == dev && *c == ctx) {
+            table.swap_remove(pos);
+        }
+        drop(table);
+        // Safety: see function safety comment; ctx is no longer reachable
+        // through the table above.
+        drop(unsafe { Box::from_raw(ctx) });
+    }
+}
+
 /// Holds a Regmap device
 pub struct Regmap<T> {
     ptr: *mut bindings::regmap,
     /// Holds the bus so that it does not get dropped until the regmap gets dropped.
-    bus: T
+    bus: T,
+    /// Policy callbacks installed for this regmap, if any, and the device
+    /// they're keyed on in the global callbacks table. Removed and freed in
+    /// `Drop`.
+    callbacks: Option<(*mut bindings::device, *mut Box<dyn RegmapCallbacks>)>,
 }
 
 #[cfg(CONFIG_REGMAP_MMIO)]
 impl<const SIZE: usize> Regmap<IoMem<SIZE>> {
     ///
     /// TODO: does this do iounmap automatically?
-    pub fn from_mmio(dev: &mut Device, mmio: IoMem<SIZE>, config: &RegmapConfig<'_>) -> Result<Self> {
+    pub fn from_mmio(
+        dev: &mut Device,
+        mmio: IoMem<SIZE>,
+        config: &RegmapConfig<'_>,
+        callbacks: Option<Box<dyn RegmapCallbacks>>,
+    ) -> Result<Self> {
+        if callbacks.is_some() {
+            check_callbacks_compatible(config)?;
+        }
+        // Safety: internal_bindings stays alive until after the init call below.
+        let (mut config, internal_bindings) = unsafe { config.to_binding()? };
+        if callbacks.is_some() {
+            install_callback_trampolines(&mut config);
+        }
+        // Safety: ctx stays alive until the error/drop paths below remove it.
+        let callbacks = callbacks
+            .map(|cb| unsafe { install_callbacks(dev.ptr, cb) }.map(|ctx| (dev.ptr, ctx)))
+            .transpose()?;
         let ptr =
             from_kernel_err_ptr(
                 // Safety: device and IOmem are legal
@@ -482,14 +716,129 @@ impl<const SIZE: usize> Regmap<IoMem<SIZE>> {
                 // config has to exist after the regmap has been initialised
                 unsafe{
                     // TODO unsupported for CONFIG_LOCKDEP
-                    let (config, internal_bindings) = config.to_binding()?;
                     bindings::__regmap_init_mmio_clk(dev.ptr, ptr::null(), mmio.ptr as *mut c_void, &config, ptr::null_mut(), ptr::null())
                 }
-            )?;
-        
+            );
+
+        let ptr = match ptr {
+            Ok(ptr) => ptr,
+            Err(e) => {
+                // Safety: this entry was installed above and init failed, so nothing else can see it.
+                unsafe { remove_callbacks(callbacks) };
+                return Err(e);
+            }
+        };
+
         Ok(Self {
             ptr,
-            bus: mmio
+            bus: mmio,
+            callbacks,
+        })
+    }
+}
+
+// TODO: these two impls assume `crate::spi::SpiDevice` / `crate::i2c::I2cClient`
+// (with a `ptr` field and a `device_mut()` accessor) and the
+// `bindings::__regmap_init_spi`/`__regmap_init_i2c` bindgen output already
+// exist; neither does in this tree, so they are gated on `any()` below in
+// addition to CONFIG_REGMAP_SPI/CONFIG_REGMAP_I2C and cannot be selected
+// for compilation. Drop the `any()` half of the gate once the spi/i2c
+// modules land for real. The bus is taken by value (not
+// `&SpiDevice`/`&I2cClient`) on purpose, matching `from_mmio`: `Regmap<T>`
+// stores the bus in its `bus: T` field so it is not dropped out from under
+// the regmap before the regmap itself is.
+#[cfg(all(CONFIG_REGMAP_SPI, any()))]
+impl Regmap<SpiDevice> {
+    /// Creates a `Regmap` backed by the given SPI device.
+    pub fn from_spi(
+        mut spi: SpiDevice,
+        config: &RegmapConfig<'_>,
+        callbacks: Option<Box<dyn RegmapCallbacks>>,
+    ) -> Result<Self> {
+        if callbacks.is_some() {
+            check_callbacks_compatible(config)?;
+        }
+        let dev_ptr = spi.device_mut().ptr;
+        // Safety: internal_bindings stays alive until after the init call below.
+        let (mut config, internal_bindings) = unsafe { config.to_binding()? };
+        if callbacks.is_some() {
+            install_callback_trampolines(&mut config);
+        }
+        // Safety: ctx stays alive until the error/drop paths below remove it.
+        let callbacks = callbacks
+            .map(|cb| unsafe { install_callbacks(dev_ptr, cb) }.map(|ctx| (dev_ptr, ctx)))
+            .transpose()?;
+        let ptr =
+            from_kernel_err_ptr(
+                // Safety: spi is a legal spi_device
+                // Safety: for the currently supported options for the config, no field of the
+                // config has to exist after the regmap has been initialised
+                unsafe{
+                    bindings::__regmap_init_spi(spi.ptr, &config, ptr::null_mut(), ptr::null())
+                }
+            );
+
+        let ptr = match ptr {
+            Ok(ptr) => ptr,
+            Err(e) => {
+                // Safety: this entry was installed above and init failed, so nothing else can see it.
+                unsafe { remove_callbacks(callbacks) };
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            ptr,
+            bus: spi,
+            callbacks,
+        })
+    }
+}
+
+#[cfg(all(CONFIG_REGMAP_I2C, any()))]
+impl Regmap<I2cClient> {
+    /// Creates a `Regmap` backed by the given I2C client.
+    pub fn from_i2c(
+        mut client: I2cClient,
+        config: &RegmapConfig<'_>,
+        callbacks: Option<Box<dyn RegmapCallbacks>>,
+    ) -> Result<Self> {
+        if callbacks.is_some() {
+            check_callbacks_compatible(config)?;
+        }
+        let dev_ptr = client.device_mut().ptr;
+        // Safety: internal_bindings stays alive until after the init call below.
+        let (mut config, internal_bindings) = unsafe { config.to_binding()? };
+        if callbacks.is_some() {
+            install_callback_trampolines(&mut config);
+        }
+        // Safety: ctx stays alive until the error/drop paths below remove it.
+        let callbacks = callbacks
+            .map(|cb| unsafe { install_callbacks(dev_ptr, cb) }.map(|ctx| (dev_ptr, ctx)))
+            .transpose()?;
+        let ptr =
+            from_kernel_err_ptr(
+                // Safety: client is a legal i2c_client
+                // Safety: for the currently supported options for the config, no field of the
+                // config has to exist after the regmap has been initialised
+                unsafe{
+                    bindings::__regmap_init_i2c(client.ptr, &config, ptr::null_mut(), ptr::null())
+                }
+            );
+
+        let ptr = match ptr {
+            Ok(ptr) => ptr,
+            Err(e) => {
+                // Safety: this entry was installed above and init failed, so nothing else can see it.
+                unsafe { remove_callbacks(callbacks) };
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            ptr,
+            bus: client,
+            callbacks,
         })
     }
 }
@@ -511,6 +860,180 @@ impl<T> Regmap<T> {
     pub fn regmap_write(&mut self, reg: u32, val: u32) -> Result<()> {
         to_result(unsafe {bindings::regmap_write(self.ptr, reg, val)})
     }
+
+    /// Performs a read-modify-write of `reg`, replacing the bits set in
+    /// `mask` with the corresponding bits of `val` and leaving the rest
+    /// untouched. Returns whether the register's value actually changed.
+    pub fn update_bits(&mut self, reg: u32, mask: u32, val: u32) -> Result<bool> {
+        let mut changed: bool = false;
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        to_result(unsafe {bindings::regmap_update_bits_check(self.ptr, reg, mask, val, &mut changed)})?;
+        Ok(changed)
+    }
+
+    /// Reads `val.len()` consecutive registers starting at `reg` into `val`.
+    pub fn bulk_read(&mut self, reg: u32, val: &mut [u32]) -> Result<()> {
+        // Safety: self.ptr is a live regmap, and val is valid for val.len() writes.
+        to_result(unsafe {
+            bindings::regmap_bulk_read(self.ptr, reg, val.as_mut_ptr() as *mut c_void, val.len())
+        })
+    }
+
+    /// Writes `val` to `val.len()` consecutive registers starting at `reg`.
+    pub fn bulk_write(&mut self, reg: u32, val: &[u32]) -> Result<()> {
+        // Safety: self.ptr is a live regmap, and val is valid for val.len() reads.
+        to_result(unsafe {
+            bindings::regmap_bulk_write(self.ptr, reg, val.as_ptr() as *const c_void, val.len())
+        })
+    }
+
+    /// Applies `seq` as a single ordered sequence of register writes,
+    /// sleeping `delay_us` after each write that requests it.
+    pub fn multi_reg_write(&mut self, seq: &[RegSequence]) -> Result<()> {
+        // Safety: self.ptr is a live regmap. RegSequence has the same layout
+        // as bindings::reg_sequence, so the cast below is valid.
+        to_result(unsafe {
+            bindings::regmap_multi_reg_write(
+                self.ptr,
+                seq.as_ptr() as *const bindings::reg_sequence,
+                seq.len() as i32,
+            )
+        })
+    }
+
+    /// Registers `seq` as a patch to be (re-)applied, via `multi_reg_write`,
+    /// whenever the register cache is synced back to hardware.
+    pub fn register_patch(&mut self, seq: &[RegSequence]) -> Result<()> {
+        // Safety: self.ptr is a live regmap. RegSequence has the same layout
+        // as bindings::reg_sequence, so the cast below is valid.
+        to_result(unsafe {
+            bindings::regmap_register_patch(
+                self.ptr,
+                seq.as_ptr() as *const bindings::reg_sequence,
+                seq.len() as i32,
+            )
+        })
+    }
+
+    /// Registers `chip` as a regmap-backed interrupt controller, demuxing
+    /// `parent_irq` into one virtual IRQ per sub-interrupt described by
+    /// `chip`. The returned handle keeps `chip`'s register description
+    /// alive and unregisters the controller when dropped.
+    pub fn add_irq_chip<'a>(
+        &'a mut self,
+        parent_irq: u32,
+        irq_flags: i32,
+        irq_base: i32,
+        chip: &RegmapIrqChip<'_>,
+    ) -> Result<RegmapIrqChipData<'a, T>> {
+        let mut data: *mut bindings::regmap_irq_chip_data = ptr::null_mut();
+        // Safety: chip_bindings.irqs is retained in the returned handle for
+        // as long as the kernel may reference it.
+        let (chip_binding, chip_bindings) = unsafe { chip.to_binding()? };
+        // regmap_add_irq_chip stores this pointer and dereferences it for
+        // the life of the controller, so it must be heap-allocated and
+        // retained (in the returned handle) rather than passed by reference
+        // to a stack temporary.
+        let chip_binding = Box::try_new(chip_binding)?;
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        to_result(unsafe {
+            bindings::regmap_add_irq_chip(
+                self.ptr,
+                parent_irq as i32,
+                irq_flags,
+                irq_base,
+                chip_binding.as_ref(),
+                &mut data,
+            )
+        })?;
+
+        Ok(RegmapIrqChipData {
+            ptr: data,
+            parent_irq,
+            _chip: chip_binding,
+            _chip_bindings: chip_bindings,
+            _map: self,
+        })
+    }
+
+    /// Writes every dirty entry of the register cache back out to hardware.
+    pub fn regcache_sync(&mut self) -> Result<()> {
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        to_result(unsafe { bindings::regcache_sync(self.ptr) })
+    }
+
+    /// Writes the dirty entries of the register cache between `min` and
+    /// `max` (inclusive) back out to hardware.
+    pub fn regcache_sync_region(&mut self, min: u32, max: u32) -> Result<()> {
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        to_result(unsafe { bindings::regcache_sync_region(self.ptr, min, max) })
+    }
+
+    /// Marks the entire register cache dirty, so that the next
+    /// [`Self::regcache_sync`] writes every cached register back out to
+    /// hardware instead of only the entries touched since the last sync.
+    /// Typically used just before a device loses power.
+    pub fn regcache_mark_dirty(&mut self) {
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        unsafe { bindings::regcache_mark_dirty(self.ptr) }
+    }
+
+    /// When enabled, regmap reads and writes only the cache, never the
+    /// underlying bus. Used while a device is powered down but still
+    /// expected to answer cached register reads.
+    pub fn set_cache_only(&mut self, enable: bool) {
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        unsafe { bindings::regcache_cache_only(self.ptr, enable) }
+    }
+
+    /// When enabled, regmap reads and writes go straight to the bus and
+    /// skip the cache entirely.
+    pub fn set_cache_bypass(&mut self, enable: bool) {
+        // Safety: self.ptr is a live regmap for the duration of this call.
+        unsafe { bindings::regcache_cache_bypass(self.ptr, enable) }
+    }
+
+    /// Polls `reg` until `cond` holds or `timeout_us` microseconds have
+    /// elapsed, sleeping `sleep_us` between reads. Performs one final read
+    /// after the deadline, so a value that becomes ready right as the
+    /// timeout expires is not missed.
+    pub fn read_poll_timeout<F: Fn(u32) -> bool>(
+        &mut self,
+        reg: u32,
+        cond: F,
+        sleep_us: u64,
+        timeout_us: u64,
+    ) -> Result<u32> {
+        // Safety: ktime_get reads the monotonic clock and has no preconditions.
+        let start = unsafe { bindings::ktime_get() };
+
+        loop {
+            let val = self.regmap_read(reg)?;
+            if cond(val) {
+                return Ok(val);
+            }
+
+            // Safety: start and the second ktime_get() call are both valid ktime_t values.
+            let elapsed_us = unsafe { bindings::ktime_us_delta(bindings::ktime_get(), start) };
+            if elapsed_us as u64 > timeout_us {
+                break;
+            }
+
+            if sleep_us > 0 {
+                // Safety: fsleep may sleep; callers of read_poll_timeout must
+                // not invoke it from atomic context, same as regmap_read itself.
+                unsafe { bindings::fsleep(sleep_us) };
+            }
+        }
+
+        // Final read to avoid racing the deadline.
+        let val = self.regmap_read(reg)?;
+        if cond(val) {
+            Ok(val)
+        } else {
+            Err(Error::from_kernel_errno(-(bindings::ETIMEDOUT as i32)))
+        }
+    }
 }
 
 impl<T> Drop for Regmap<T> {
@@ -519,6 +1042,215 @@ impl<T> Drop for Regmap<T> {
     /// under the from_ptr preconditions.
     fn drop(&mut self) {
         unsafe {bindings::regmap_exit(self.ptr)}
+        // Safety: regmap_exit above has returned, so the trampolines can no
+        // longer look this entry up; nothing else holds a reference to it.
+        unsafe { remove_callbacks(self.callbacks) };
         //core::mem::drop(self.bus)
     }
 }
+
+/// A single contiguous bitfield within one register of a [`Regmap`].
+///
+/// Binds a register plus a `[lsb, msb]` bit range so that reads and writes
+/// only touch that field; regmap takes care of the shift and the
+/// read-modify-write against the rest of the register.
+///
+/// TODO: only the non-devm allocation path is wired up for now; add
+/// `devm_regmap_field_alloc` once `Regmap` itself grows a dev-managed
+/// constructor (see the TODO at the top of this file).
+pub struct RegmapField<'a, T> {
+    ptr: *mut bindings::regmap_field,
+    /// Borrows the parent map so the field cannot outlive it.
+    _map: &'a mut Regmap<T>,
+}
+
+impl<'a, T> RegmapField<'a, T> {
+    /// Allocates a field spanning bits `lsb` through `msb` (inclusive) of
+    /// `reg` on `map`.
+    pub fn alloc(map: &'a mut Regmap<T>, reg: u32, lsb: u32, msb: u32) -> Result<Self> {
+        let field_desc = bindings::reg_field {
+            reg,
+            lsb,
+            msb,
+            id_offset: 0,
+            id_size: 0,
+        };
+
+        let ptr = from_kernel_err_ptr(
+            // Safety: map.ptr is a live regmap for the duration of this call,
+            // and outlives the returned RegmapField via the borrow above.
+            unsafe { bindings::regmap_field_alloc(map.ptr, field_desc) }
+        )?;
+
+        Ok(Self { ptr, _map: map })
+    }
+
+    /// Reads the field's current value, already shifted down to bit 0.
+    ///
+    /// Takes `&mut self` because reading from a field's register can have
+    /// side effects (resetting flags), matching [`Regmap::regmap_read`].
+    pub fn read(&mut self) -> Result<u32> {
+        let mut val: u32 = 0;
+        // Safety: self.ptr is a live regmap_field for the lifetime of self.
+        match unsafe { bindings::regmap_field_read(self.ptr, &mut val) } {
+            0 => Ok(val),
+            e => Err(Error::from_kernel_errno(e)),
+        }
+    }
+
+    /// Writes `val` into the field, leaving the rest of the register
+    /// untouched.
+    pub fn write(&mut self, val: u32) -> Result<()> {
+        // Safety: self.ptr is a live regmap_field for the lifetime of self.
+        to_result(unsafe { bindings::regmap_field_write(self.ptr, val) })
+    }
+}
+
+impl<'a, T> Drop for RegmapField<'a, T> {
+    /// Safety: self.ptr was allocated in `alloc` and is not read after this.
+    fn drop(&mut self) {
+        unsafe { bindings::regmap_field_free(self.ptr) }
+    }
+}
+
+/// Describes one interrupt bit within a [`RegmapIrqChip`]: which register
+/// (as an offset from the chip's `status_base`/`mask_base`/`ack_base`) and
+/// which bit within that register.
+pub struct RegmapIrq {
+    /// Offset, in registers, of the status/mask/ack register this bit lives in.
+    pub reg_offset: u32,
+    /// Bit mask identifying this interrupt within its register.
+    pub mask: u32,
+}
+
+impl RegmapIrq {
+    fn to_binding(&self) -> bindings::regmap_irq {
+        bindings::regmap_irq {
+            reg_offset: self.reg_offset,
+            mask: self.mask,
+            type_reg_offset: 0,
+            type_rising_mask: 0,
+            type_falling_mask: 0,
+        }
+    }
+}
+
+/// Holds the binding equivalents of the Rust collections in RegmapIrqChip.
+struct RegmapIrqChipBindings {
+    irqs: Vec<bindings::regmap_irq>,
+}
+
+/// Declarative description of a regmap-backed interrupt controller: a
+/// device's status/mask/ack registers plus one entry per sub-interrupt.
+/// regmap demuxes the parent IRQ into a virtual IRQ per entry, see
+/// `regmap-irq.c`.
+///
+/// Missing options, as with [`RegmapConfig`], are as of yet unsupported.
+pub struct RegmapIrqChip<'a> {
+    /// Optional descriptive name for diagnostics.
+    name: Option<&'static CStr>,
+    /// Address of the first status register.
+    status_base: u32,
+    /// Address of the first mask register.
+    mask_base: u32,
+    /// Address of the first ack register.
+    ack_base: u32,
+    /// Number of registers spanned by the status/mask/ack regions.
+    num_regs: i32,
+    /// One entry per sub-interrupt.
+    irqs: &'a [RegmapIrq],
+}
+
+impl<'a> RegmapIrqChip<'a> {
+    pub fn new(status_base: u32, mask_base: u32, ack_base: u32, num_regs: i32, irqs: &'a [RegmapIrq]) -> Self {
+        RegmapIrqChip {
+            name: None,
+            status_base,
+            mask_base,
+            ack_base,
+            num_regs,
+            irqs,
+        }
+    }
+
+    /// Converts to binding.
+    /// Safety: self.name must exist as long as the return value
+    unsafe fn to_binding(&self) -> Result<(bindings::regmap_irq_chip, RegmapIrqChipBindings)> {
+        unsafe {
+            let mut irqs = Vec::try_with_capacity(self.irqs.len())?;
+            for irq in self.irqs {
+                irqs.try_push(irq.to_binding())?;
+            }
+            let binds = RegmapIrqChipBindings { irqs };
+
+            let chip = bindings::regmap_irq_chip {
+                name: if let Some(n) = self.name {n.as_char_ptr()} else {ptr::null()},
+                main_status: 0,
+                num_main_status_bits: 0,
+                irq_reg_stride: 0,
+                status_base: self.status_base,
+                mask_base: self.mask_base,
+                unmask_base: 0,
+                ack_base: self.ack_base,
+                wake_base: 0,
+                type_base: 0,
+                status_invert: false,
+                mask_invert: false,
+                ack_invert: false,
+                wake_invert: false,
+                type_invert: false,
+                type_in_mask: false,
+                clear_on_unmask: false,
+                not_fixed_stride: false,
+                runtime_pm: false,
+                num_regs: self.num_regs,
+                irqs: binds.irqs.as_ptr(),
+                num_irqs: binds.irqs.len() as i32,
+                num_type_reg: 0,
+                type_reg_stride: 0,
+                handle_pre_irq: None,
+                handle_post_irq: None,
+                set_type_virt: None,
+                irq_drv_data: ptr::null_mut(),
+            };
+            Ok((chip, binds))
+        }
+    }
+}
+
+/// A registered regmap-backed interrupt controller, demuxing the parent IRQ
+/// supplied to [`Regmap::add_irq_chip`] into per-bit virtual IRQs.
+pub struct RegmapIrqChipData<'a, T> {
+    ptr: *mut bindings::regmap_irq_chip_data,
+    parent_irq: u32,
+    /// Keeps the `regmap_irq_chip` description itself alive: `regmap` stores
+    /// this pointer (`d->chip = chip`) and the threaded IRQ handler
+    /// dereferences it for as long as the controller is registered, so it
+    /// must live in the heap, not as a stack temporary.
+    _chip: Box<bindings::regmap_irq_chip>,
+    /// Keeps the per-interrupt descriptions alive; `regmap_add_irq_chip`
+    /// only borrows the pointer, it does not copy the array.
+    _chip_bindings: RegmapIrqChipBindings,
+    /// Borrows the parent map so the chip cannot outlive it.
+    _map: &'a Regmap<T>,
+}
+
+impl<'a, T> RegmapIrqChipData<'a, T> {
+    /// Returns the virtual IRQ number for the sub-interrupt at `index`
+    /// within the chip's `irqs` array.
+    pub fn irq_chip_get_virq(&self, index: u32) -> Result<u32> {
+        // Safety: self.ptr is a live regmap_irq_chip_data for the lifetime of self.
+        match unsafe { bindings::regmap_irq_get_virq(self.ptr, index as i32) } {
+            e if e < 0 => Err(Error::from_kernel_errno(e)),
+            virq => Ok(virq as u32),
+        }
+    }
+}
+
+impl<'a, T> Drop for RegmapIrqChipData<'a, T> {
+    /// Safety: self.ptr was returned by `regmap_add_irq_chip` and is not used
+    /// again after this.
+    fn drop(&mut self) {
+        unsafe { bindings::regmap_del_irq_chip(self.parent_irq as i32, self.ptr) }
+    }
+}